@@ -0,0 +1,62 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::process::ExitCode;
+
+use huffman::{compress_stream, decompress_stream, CompressionMode};
+
+enum Mode {
+    Compress,
+    Decompress,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+
+    let (mode, input_path, output_path) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("usage: huffman -c|--compress <input> <output>");
+            eprintln!("       huffman -d|--decompress <input> <output>");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(mode, input_path, output_path) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Mode, &str, &str), String> {
+    let [_, flag, input_path, output_path] = args else {
+        return Err("expected exactly 3 arguments: <flag> <input> <output>".to_string());
+    };
+
+    let mode = match flag.as_str() {
+        "-c" | "--compress" => Mode::Compress,
+        "-d" | "--decompress" => Mode::Decompress,
+        other => return Err(format!("unknown flag '{other}'")),
+    };
+
+    Ok((mode, input_path, output_path))
+}
+
+fn run(mode: Mode, input_path: &str, output_path: &str) -> io::Result<()> {
+    let input = BufReader::new(File::open(input_path)?);
+    let output = BufWriter::new(File::create(output_path)?);
+
+    let result = match mode {
+        Mode::Compress => compress_stream(input, output, CompressionMode::Static),
+        Mode::Decompress => decompress_stream(input, output),
+    };
+
+    // Container framing errors (truncated or corrupted `.huf` files) are
+    // ordinary user-input mistakes, not bugs — report which file they came
+    // from rather than letting them panic the process.
+    result.map_err(|err| io::Error::new(err.kind(), format!("{input_path}: {err}")))
+}
@@ -3,169 +3,668 @@ use std::{
     collections::{BinaryHeap, HashMap},
     fmt::Debug,
     fmt::Formatter,
+    io::{self, Read, Write},
 };
 
-type NodeRef = Option<Box<Node>>;
 type HuffmanCodes = HashMap<u8, HuffmanCode>;
 
-#[derive(PartialEq, Debug, Eq, Clone)]
+const MAX_SYMBOLS: usize = 256;
+const NONE: usize = usize::MAX;
+
+// A flat arena: leaves for byte values live at their own fixed index
+// (`0..MAX_SYMBOLS`), so a leaf never needs to carry its symbol explicitly —
+// the index *is* the symbol. Internal (merged) nodes are appended after and
+// hold their children's indices. This avoids the `Box` allocation and
+// per-merge `clone()` a pointer-based tree would need, and doubles as the
+// shape `decompress` walks one bit at a time.
+#[derive(Clone, Copy)]
 struct Node {
-    left: NodeRef,
-    right: NodeRef,
+    left: usize,
+    right: usize,
+}
+
+impl Node {
+    fn leaf() -> Self {
+        Node {
+            left: NONE,
+            right: NONE,
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.left == NONE && self.right == NONE
+    }
+}
+
+struct HeapEntry {
     weight: i64,
-    symbol: Option<u8>,
+    index: usize,
 }
 
-impl Ord for Node {
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.weight == other.weight
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.weight.cmp(&other.weight)
     }
 }
 
-impl PartialOrd for Node {
+impl PartialOrd for HeapEntry {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Node {
-    fn new(weight: i64, symbol: u8) -> Self {
-        Node {
-            left: None,
-            right: None,
-            weight,
-            symbol: Some(symbol),
-        }
-    }
-}
-
-#[derive(Default, PartialEq, Clone)]
+// `val` holds the code bits right-aligned, e.g. a 3-bit code `101` is stored
+// as `0b101`. This supports codes up to 64 bits, far beyond what a 256-symbol
+// alphabet produces in practice.
+#[derive(Default, PartialEq, Eq, Clone)]
 struct HuffmanCode {
-    code: u8,
+    val: u64,
     len: u8,
 }
 
 impl Debug for HuffmanCode {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:0width$b}", self.code, width = self.len as usize)
+        write!(f, "{:0width$b}", self.val, width = self.len as usize)
     }
 }
 
 impl HuffmanCode {
     fn push(mut self, bit: u8) -> Self {
-        self.code = (self.code << 1) | bit;
+        self.val = (self.val << 1) | bit as u64;
         self.len += 1;
         self
     }
 
     fn bits(&self) -> Vec<u8> {
         (0..self.len)
-            .map(|i| (self.code >> (self.len - i - 1)) & 1)
+            .map(|i| ((self.val >> (self.len - i - 1)) & 1) as u8)
             .collect()
     }
+}
+
+/// Builds Huffman codes for a string, taken as its UTF-8 bytes.
+#[cfg(test)]
+fn generate_huffman_str(s: &str) -> HuffmanCodes {
+    generate_huffman(s.as_bytes())
+}
 
-    fn clear(&mut self) {
-        self.code = 0;
-        self.len = 0;
+fn generate_huffman(data: &[u8]) -> HuffmanCodes {
+    huffman_codes_from_frequencies(byte_frequencies(data))
+}
+
+fn byte_frequencies(data: &[u8]) -> [i64; 256] {
+    let mut frequencies = [0i64; 256];
+    for &byte in data {
+        frequencies[byte as usize] += 1;
     }
+    frequencies
 }
 
-fn generate_huffman(s: &str) -> HuffmanCodes {
-    let mut min_heap: BinaryHeap<Reverse<Node>> = BinaryHeap::new();
-    s.chars()
-        .fold(HashMap::new(), |mut acc, c| {
-            let count = acc.get(&c).unwrap_or(&0) + 1;
-            acc.insert(c, count);
-            acc
-        })
-        .into_iter()
-        .for_each(|(c, count)| min_heap.push(Reverse(Node::new(count as i64, c as u8))));
+// Shared by `generate_huffman`, which counts frequencies from raw data, and
+// the adaptive coders, which rebuild from their running counts. Frequencies are
+// folded into the heap in ascending symbol order so the resulting tree is
+// fully determined by the counts, letting encoder and decoder rebuild the
+// identical tree independently.
+fn huffman_codes_from_frequencies(frequencies: [i64; 256]) -> HuffmanCodes {
+    let mut nodes: Vec<Node> = (0..MAX_SYMBOLS).map(|_| Node::leaf()).collect();
+
+    let mut min_heap: BinaryHeap<Reverse<HeapEntry>> = BinaryHeap::new();
+    frequencies
+        .iter()
+        .enumerate()
+        .filter(|&(_, &count)| count > 0)
+        .for_each(|(index, &weight)| min_heap.push(Reverse(HeapEntry { weight, index })));
 
     let heap_len = min_heap.len();
 
+    // An empty input (or all-zero frequency table) has no symbols to assign
+    // codes to at all — nothing to merge, and no root to derive one from.
+    if heap_len == 0 {
+        return HashMap::new();
+    }
+
+    // A single distinct symbol never merges, so it would sit at the root
+    // with code length 0 — indistinguishable from "absent" once that length
+    // is stored in a canonical length table. Give it the shortest real code,
+    // one bit, instead of deriving a length from tree depth.
+    if heap_len == 1 {
+        let symbol = min_heap.pop().unwrap().0.index as u8;
+        return HashMap::from([(symbol, HuffmanCode::default().push(0))]);
+    }
+
     let root = loop {
         match (min_heap.pop(), min_heap.pop()) {
-            (Some(node), Some(node1)) => {
-                let new_node = Node {
-                    left: Some(Box::new(node.0.clone())),
-                    right: Some(Box::new(node1.0.clone())),
-                    weight: node.0.weight + node1.0.weight,
-                    symbol: None,
-                };
-
-                min_heap.push(Reverse(new_node));
+            (Some(a), Some(b)) => {
+                let weight = a.0.weight + b.0.weight;
+                let index = nodes.len();
+                nodes.push(Node {
+                    left: a.0.index,
+                    right: b.0.index,
+                });
+
+                min_heap.push(Reverse(HeapEntry { weight, index }));
             }
-            (Some(root), _) => break root,
+            (Some(root), None) => break root.0.index,
             _ => panic!("no root found"),
         }
     };
 
     let mut huffman_codes = HashMap::new();
-    create_codes(HuffmanCode::default(), root.0, &mut huffman_codes);
+    create_codes(&nodes, root, HuffmanCode::default(), &mut huffman_codes);
 
     debug_assert_eq!(heap_len, huffman_codes.len());
 
     huffman_codes
 }
 
-fn create_codes(code: HuffmanCode, mut root: Node, codes: &mut HuffmanCodes) {
-    while let (Some(left), Some(right)) = (root.left.take(), root.right.take()) {
-        create_codes(code.clone().push(0), *left, codes);
-        create_codes(code.clone().push(1), *right, codes);
+fn create_codes(nodes: &[Node], index: usize, code: HuffmanCode, codes: &mut HuffmanCodes) {
+    let node = &nodes[index];
+    if node.is_leaf() {
+        codes.insert(index as u8, code);
+    } else {
+        create_codes(nodes, node.left, code.clone().push(0), codes);
+        create_codes(nodes, node.right, code.push(1), codes);
     }
+}
 
-    if let Some(symbol) = root.symbol {
-        codes.insert(symbol, code);
-    }
+/// Compresses a string, taken as its UTF-8 bytes.
+#[cfg(test)]
+fn compress_str(s: &str, huffman_codes: &HuffmanCodes) -> Vec<u8> {
+    compress(s.as_bytes(), huffman_codes)
 }
 
-fn compress(s: &str, huffman_codes: &HuffmanCodes) -> Vec<u8> {
+#[cfg(test)]
+fn compress(data: &[u8], huffman_codes: &HuffmanCodes) -> Vec<u8> {
     let mut compressed = Vec::new();
-    let mut buffer = 0;
-    let mut buffer_len = 0;
+    compress_into(data, huffman_codes, &mut compressed).expect("writing to a Vec<u8> never fails");
+    compressed
+}
+
+// Packs bits into bytes and flushes each one to `writer` as soon as it fills
+// up, rather than collecting the whole bitstream before handing it over —
+// this is what lets `compress_stream` in `Static` mode forward bytes to a
+// `Write` as they're produced instead of buffering the entire compressed
+// output in memory.
+struct BitWriter<'w, W: Write> {
+    writer: &'w mut W,
+    buffer: u8,
+    buffer_len: u8,
+}
+
+impl<'w, W: Write> BitWriter<'w, W> {
+    fn new(writer: &'w mut W) -> Self {
+        BitWriter {
+            writer,
+            buffer: 0,
+            buffer_len: 0,
+        }
+    }
+
+    fn write_bit(&mut self, bit: u8) -> io::Result<()> {
+        self.buffer = (self.buffer << 1) | bit;
+        self.buffer_len += 1;
+
+        if self.buffer_len == 8 {
+            self.writer.write_all(&[self.buffer])?;
+            self.buffer = 0;
+            self.buffer_len = 0;
+        }
 
-    for c in s.chars() {
-        let code = huffman_codes.get(&(c as u8)).unwrap();
+        Ok(())
+    }
+
+    fn write_code(&mut self, code: &HuffmanCode) -> io::Result<()> {
         for bit in code.bits() {
-            buffer = (buffer << 1) | bit;
-            buffer_len += 1;
+            self.write_bit(bit)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> io::Result<()> {
+        if self.buffer_len > 0 {
+            self.writer.write_all(&[self.buffer << (8 - self.buffer_len)])?;
+        }
+        Ok(())
+    }
+}
+
+// Reads bits one at a time from any `Read`, letting a symbol's code span a
+// byte boundary without the caller needing to know where. Generic over `R`
+// (rather than tied to a byte slice) so it can pull bytes on demand from a
+// `compress_stream`-style reader instead of requiring the whole compressed
+// buffer to be materialized up front — `&[u8]` satisfies `Read` too, so the
+// in-memory callers pass a slice straight through.
+struct BitReader<R: Read> {
+    reader: R,
+    byte: u8,
+    bit_index: u8,
+}
 
-            if buffer_len == 8 {
-                compressed.push(buffer);
-                buffer = 0;
-                buffer_len = 0;
+impl<R: Read> BitReader<R> {
+    fn new(reader: R) -> Self {
+        BitReader {
+            reader,
+            byte: 0,
+            bit_index: 8,
+        }
+    }
+
+    fn next_bit(&mut self) -> io::Result<Option<u8>> {
+        if self.bit_index == 8 {
+            let mut buf = [0u8; 1];
+            if self.reader.read(&mut buf)? == 0 {
+                return Ok(None);
             }
+            self.byte = buf[0];
+            self.bit_index = 0;
         }
+
+        let bit = (self.byte >> (7 - self.bit_index)) & 1;
+        self.bit_index += 1;
+
+        Ok(Some(bit))
     }
+}
+
+fn compress_into<W: Write>(
+    data: &[u8],
+    huffman_codes: &HuffmanCodes,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut bit_writer = BitWriter::new(writer);
 
-    if buffer_len > 0 {
-        compressed.push(buffer << (8 - buffer_len));
+    for &byte in data {
+        let code = huffman_codes.get(&byte).unwrap();
+        bit_writer.write_code(code)?;
     }
 
-    compressed
+    bit_writer.finish()
+}
+
+// Builds the same leaf-indexed arena `huffman_codes_from_frequencies` uses,
+// but from code bit patterns rather than weights: each code is inserted bit
+// by bit, allocating internal nodes on demand, until it terminates at the
+// leaf for its symbol.
+fn build_decode_tree(huffman_codes: &HuffmanCodes) -> (Vec<Node>, usize) {
+    let mut nodes: Vec<Node> = (0..MAX_SYMBOLS).map(|_| Node::leaf()).collect();
+    let root = nodes.len();
+    nodes.push(Node::leaf());
+
+    for (&symbol, code) in huffman_codes {
+        let mut current = root;
+
+        let bits = code.bits();
+        for (i, bit) in bits.iter().enumerate() {
+            let is_last = i == bits.len() - 1;
+            let existing = if *bit == 0 {
+                nodes[current].left
+            } else {
+                nodes[current].right
+            };
+
+            let next = if is_last {
+                symbol as usize
+            } else if existing != NONE {
+                existing
+            } else {
+                let index = nodes.len();
+                nodes.push(Node::leaf());
+                index
+            };
+
+            if *bit == 0 {
+                nodes[current].left = next;
+            } else {
+                nodes[current].right = next;
+            }
+
+            current = next;
+        }
+    }
+
+    (nodes, root)
 }
 
+#[cfg(test)]
 fn decompress(compressed: &[u8], huffman_codes: &HuffmanCodes, output_len: usize) -> Vec<u8> {
-    let mut decompressed = vec![];
-    let mut buffer = HuffmanCode::default();
+    let mut decompressed = Vec::with_capacity(output_len);
+    decompress_into(compressed, huffman_codes, output_len, &mut decompressed)
+        .expect("writing to a Vec<u8> never fails");
+    decompressed
+}
+
+// Emits each decoded byte to `writer` as soon as a leaf is reached, and pulls
+// bits from `reader` as soon as one is needed, so `decompress_stream` can
+// stream both ends without collecting the whole compressed input or
+// decompressed output in memory.
+fn decompress_into<R: Read, W: Write>(
+    reader: R,
+    huffman_codes: &HuffmanCodes,
+    output_len: usize,
+    writer: &mut W,
+) -> io::Result<()> {
+    let (nodes, root) = build_decode_tree(huffman_codes);
+    let mut bits = BitReader::new(reader);
+
+    for _ in 0..output_len {
+        let mut current = root;
+        while current >= MAX_SYMBOLS {
+            let bit = bits.next_bit()?.ok_or_else(truncated_stream_error)?;
+            current = if bit == 0 {
+                nodes[current].left
+            } else {
+                nodes[current].right
+            };
+            if current == NONE {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "compressed stream contains a code missing from the code table",
+                ));
+            }
+        }
+        writer.write_all(&[current as u8])?;
+    }
+
+    Ok(())
+}
+
+// The container format is untrusted input — a truncated or hand-edited
+// artifact can run out of bits mid-codeword, which is a malformed-input
+// error to report, not a bug to panic on.
+fn truncated_stream_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "compressed stream ended before all symbols were decoded",
+    )
+}
+
+/// Builds canonical Huffman codes from per-symbol code lengths alone.
+///
+/// Symbols are sorted by `(length, symbol value)` ascending; the first gets
+/// code `0`, and each subsequent code is the previous plus one, left-shifted
+/// by however many bits the length grew. Because the rule is deterministic,
+/// encoder and decoder derive identical codes from just the length table —
+/// the bit patterns themselves never need to be stored.
+fn canonical_codes(lengths: &[u8; 256]) -> HuffmanCodes {
+    let mut symbols: Vec<(u8, u8)> = lengths
+        .iter()
+        .enumerate()
+        .filter(|&(_, &len)| len > 0)
+        .map(|(symbol, &len)| (symbol as u8, len))
+        .collect();
+    symbols.sort_by_key(|&(symbol, len)| (len, symbol));
+
+    let mut codes = HashMap::new();
+    let mut code: u64 = 0;
+    let mut prev_len: Option<u8> = None;
+
+    for (symbol, len) in symbols {
+        if let Some(prev_len) = prev_len {
+            code = (code + 1) << (len - prev_len);
+        }
+        codes.insert(symbol, HuffmanCode { val: code, len });
+        prev_len = Some(len);
+    }
+
+    codes
+}
 
-    'outer: for byte in compressed {
-        for i in 0..8 {
-            let bit = (byte >> (7 - i)) & 1;
-            buffer = buffer.push(bit);
+/// Selects how `encode`/`compress_stream` assign codes.
+///
+/// `Static` makes a full pass over the input to build a frequency-weighted
+/// tree, then transmits canonical code lengths in the header so the decoder
+/// can rebuild the same tree without the original data. `Adaptive` never
+/// transmits a table at all: encoder and decoder both start from a uniform
+/// model and update it the same way after each symbol, stepping the tree
+/// through an identical sequence of states in lockstep.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompressionMode {
+    Static,
+    Adaptive,
+}
 
-            if let Some(symbol) = huffman_codes.iter().find(|(_, code)| *code == &buffer) {
-                decompressed.push(*symbol.0);
-                buffer.clear();
+const MODE_TAG_STATIC: u8 = 0;
+const MODE_TAG_ADAPTIVE: u8 = 1;
+
+/// Compresses `data` into a self-describing container: a header carrying
+/// everything `decode` needs to rebuild the tree and know where to stop,
+/// followed by the packed bitstream. The resulting buffer is a complete
+/// compressed artifact that needs no side-channel to decompress.
+///
+/// Header layout: a one-byte mode tag, then `original_len: u64`
+/// little-endian, then — for `Static` only — `lengths: [u8; 256]` (the
+/// canonical code length of each byte value, `0` if absent).
+fn encode(data: &[u8], mode: CompressionMode) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_into(data, mode, &mut out).expect("writing to a Vec<u8> never fails");
+    out
+}
 
-                if decompressed.len() == output_len {
-                    break 'outer;
-                }
+fn encode_into<W: Write>(data: &[u8], mode: CompressionMode, writer: &mut W) -> io::Result<()> {
+    match mode {
+        CompressionMode::Static => {
+            let tree_codes = generate_huffman(data);
+
+            let mut lengths = [0u8; 256];
+            for (&symbol, code) in tree_codes.iter() {
+                lengths[symbol as usize] = code.len;
             }
+
+            let huffman_codes = canonical_codes(&lengths);
+
+            writer.write_all(&[MODE_TAG_STATIC])?;
+            writer.write_all(&(data.len() as u64).to_le_bytes())?;
+            writer.write_all(&lengths)?;
+            compress_into(data, &huffman_codes, writer)
+        }
+        CompressionMode::Adaptive => {
+            writer.write_all(&[MODE_TAG_ADAPTIVE])?;
+            writer.write_all(&(data.len() as u64).to_le_bytes())?;
+            adaptive_compress_into(data, writer)
         }
     }
+}
 
-    decompressed
+/// Reads the mode tag and original length shared by both container layouts,
+/// returning them alongside whatever bytes follow.
+#[cfg(test)]
+fn parse_header(bytes: &[u8]) -> (u8, usize, &[u8]) {
+    let mode_tag = bytes[0];
+    let original_len = u64::from_le_bytes(bytes[1..9].try_into().unwrap()) as usize;
+    (mode_tag, original_len, &bytes[9..])
+}
+
+/// Decompresses a container produced by `encode`.
+#[cfg(test)]
+fn decode(bytes: &[u8]) -> Vec<u8> {
+    let (mode_tag, original_len, rest) = parse_header(bytes);
+
+    match mode_tag {
+        MODE_TAG_STATIC => {
+            let mut lengths = [0u8; 256];
+            lengths.copy_from_slice(&rest[..256]);
+            let huffman_codes = canonical_codes(&lengths);
+            decompress(&rest[256..], &huffman_codes, original_len)
+        }
+        MODE_TAG_ADAPTIVE => {
+            let mut out = Vec::with_capacity(original_len);
+            adaptive_decompress_into(rest, original_len, &mut out)
+                .expect("writing to a Vec<u8> never fails");
+            out
+        }
+        other => panic!("unknown compression mode tag: {other}"),
+    }
+}
+
+// Rebuilding a 256-leaf tree from scratch is the expensive part of adapting
+// online, so rather than paying for it after every symbol, both encoder and
+// decoder only rebuild when the count of symbols processed so far crosses a
+// power of two. The schedule depends only on how many symbols have been
+// seen, not on their values, so both sides rebuild at exactly the same
+// points without exchanging anything extra — and the exponentially widening
+// gaps mean the tree still tracks the real distribution closely, at O(log n)
+// rebuilds instead of O(n).
+fn should_rebuild(symbols_seen: usize) -> bool {
+    symbols_seen.is_power_of_two()
+}
+
+/// Encodes one symbol at a time with a tree that adapts to frequencies seen
+/// so far, so the container never needs to carry a table. The model starts
+/// uniform (every symbol weight `1`) rather than empty, which sidesteps
+/// needing a "not yet transmitted" escape code for the first occurrence of
+/// each symbol.
+fn adaptive_compress_into<W: Write>(data: &[u8], writer: &mut W) -> io::Result<()> {
+    let mut frequencies = [1i64; 256];
+    let mut bit_writer = BitWriter::new(writer);
+    let mut huffman_codes = huffman_codes_from_frequencies(frequencies);
+
+    for (seen, &byte) in data.iter().enumerate() {
+        bit_writer.write_code(&huffman_codes[&byte])?;
+        frequencies[byte as usize] += 1;
+
+        if should_rebuild(seen + 1) {
+            huffman_codes = huffman_codes_from_frequencies(frequencies);
+        }
+    }
+
+    bit_writer.finish()
+}
+
+/// Mirrors `adaptive_compress_into`: starts from the same uniform model and
+/// rebuilds its decode tree on the same schedule, so encoder and decoder
+/// never drift apart. Reads bits from `reader` on demand rather than
+/// requiring the compressed bytes up front.
+fn adaptive_decompress_into<R: Read, W: Write>(
+    reader: R,
+    output_len: usize,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut frequencies = [1i64; 256];
+    let mut bits = BitReader::new(reader);
+    let (mut nodes, mut root) = build_decode_tree(&huffman_codes_from_frequencies(frequencies));
+
+    for seen in 0..output_len {
+        let mut current = root;
+        while current >= MAX_SYMBOLS {
+            let bit = bits.next_bit()?.ok_or_else(truncated_stream_error)?;
+            current = if bit == 0 {
+                nodes[current].left
+            } else {
+                nodes[current].right
+            };
+        }
+
+        writer.write_all(&[current as u8])?;
+        frequencies[current] += 1;
+
+        if should_rebuild(seen + 1) {
+            (nodes, root) = build_decode_tree(&huffman_codes_from_frequencies(frequencies));
+        }
+    }
+
+    Ok(())
+}
+
+/// Ratio of `data`'s length to its compressed container size under `mode`,
+/// for reporting compression effectiveness (e.g. from a benchmark).
+pub fn compression_ratio(data: &[u8], mode: CompressionMode) -> f64 {
+    data.len() as f64 / encode(data, mode).len() as f64
+}
+
+// Runs the same adaptive coding as `adaptive_compress_into`, but reads
+// `reader` one byte at a time, tallying how many symbols were seen. Unlike `Static`, the
+// model never needs a second look at the input, so the raw bytes don't need
+// to be buffered at all — only the packed bitstream does, since the header
+// still has to carry the original length ahead of it.
+fn adaptive_compress_stream<R: Read>(mut reader: R) -> io::Result<(u64, Vec<u8>)> {
+    let mut frequencies = [1i64; 256];
+    let mut body = Vec::new();
+    let mut bit_writer = BitWriter::new(&mut body);
+    let mut huffman_codes = huffman_codes_from_frequencies(frequencies);
+    let mut len = 0u64;
+    let mut byte = [0u8; 1];
+
+    loop {
+        if reader.read(&mut byte)? == 0 {
+            break;
+        }
+
+        bit_writer.write_code(&huffman_codes[&byte[0]])?;
+        frequencies[byte[0] as usize] += 1;
+        len += 1;
+
+        if should_rebuild(len as usize) {
+            huffman_codes = huffman_codes_from_frequencies(frequencies);
+        }
+    }
+
+    bit_writer.finish()?;
+    Ok((len, body))
+}
+
+/// Streams `reader` through the container format into `writer`. `Static`
+/// still reads the input fully into memory, since its code lengths depend on
+/// frequencies counted across the whole input before any code can be
+/// assigned; `Adaptive` needs no such lookahead, so it reads one byte at a
+/// time from `reader` without ever holding the raw input in memory.
+pub fn compress_stream<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    mode: CompressionMode,
+) -> io::Result<()> {
+    match mode {
+        CompressionMode::Static => {
+            let mut data = Vec::new();
+            reader.read_to_end(&mut data)?;
+            encode_into(&data, mode, &mut writer)
+        }
+        CompressionMode::Adaptive => {
+            let (len, body) = adaptive_compress_stream(reader)?;
+            writer.write_all(&[MODE_TAG_ADAPTIVE])?;
+            writer.write_all(&len.to_le_bytes())?;
+            writer.write_all(&body)
+        }
+    }
+}
+
+/// Streams a container produced by `compress_stream` from `reader` into
+/// `writer`. Only the fixed-size header (and, for `Static`, the length
+/// table) is read eagerly; the bitstream itself is pulled one byte at a time
+/// as bits are needed, so neither the compressed input nor the decompressed
+/// output is ever buffered whole.
+pub fn decompress_stream<R: Read, W: Write>(mut reader: R, mut writer: W) -> io::Result<()> {
+    let mut header = [0u8; 9];
+    reader.read_exact(&mut header)?;
+    let mode_tag = header[0];
+    let original_len = u64::from_le_bytes(header[1..9].try_into().unwrap()) as usize;
+
+    match mode_tag {
+        MODE_TAG_STATIC => {
+            let mut lengths = [0u8; 256];
+            reader.read_exact(&mut lengths)?;
+            if lengths.iter().any(|&len| len > 64) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "code length table holds a length over 64 bits",
+                ));
+            }
+            let huffman_codes = canonical_codes(&lengths);
+            decompress_into(reader, &huffman_codes, original_len, &mut writer)
+        }
+        MODE_TAG_ADAPTIVE => adaptive_decompress_into(reader, original_len, &mut writer),
+        other => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown compression mode tag: {other}"),
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -175,8 +674,8 @@ mod tests {
     #[test]
     fn test_generate_huffman() {
         let test_string = "huihuihyf7d6d6d64s4seseseawa23q2a4";
-        let huffman_codes = generate_huffman(test_string);
-        let comressed = compress(test_string, &huffman_codes);
+        let huffman_codes = generate_huffman_str(test_string);
+        let comressed = compress_str(test_string, &huffman_codes);
 
         huffman_codes.iter().for_each(|(k, v)| {
             println!("{:?} {:?}", *k as char, v);
@@ -192,4 +691,75 @@ mod tests {
             decompress(&comressed, &huffman_codes, test_string.len())
         );
     }
+
+    #[test]
+    fn test_generate_huffman_bytes_roundtrip() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        let huffman_codes = generate_huffman(&data);
+        let compressed = compress(&data, &huffman_codes);
+
+        assert_eq!(data, decompress(&compressed, &huffman_codes, data.len()));
+    }
+
+    #[test]
+    fn test_canonical_codes_match_lengths() {
+        let mut lengths = [0u8; 256];
+        lengths[b'a' as usize] = 1;
+        lengths[b'b' as usize] = 2;
+        lengths[b'c' as usize] = 2;
+
+        let codes = canonical_codes(&lengths);
+
+        assert_eq!(codes[&b'a'].len, 1);
+        assert_eq!(codes[&b'b'].len, 2);
+        assert_eq!(codes[&b'c'].len, 2);
+        assert_eq!(codes[&b'a'].val, 0b0);
+        assert_eq!(codes[&b'b'].val, 0b10);
+        assert_eq!(codes[&b'c'].val, 0b11);
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let data = b"huihuihyf7d6d6d64s4seseseawa23q2a4".to_vec();
+
+        for mode in [CompressionMode::Static, CompressionMode::Adaptive] {
+            let container = encode(&data, mode);
+            assert_eq!(data, decode(&container));
+        }
+    }
+
+    #[test]
+    fn test_compress_decompress_stream_roundtrip() {
+        let data = b"huihuihyf7d6d6d64s4seseseawa23q2a4".to_vec();
+
+        let mut container = Vec::new();
+        compress_stream(data.as_slice(), &mut container, CompressionMode::Adaptive).unwrap();
+
+        let mut output = Vec::new();
+        decompress_stream(container.as_slice(), &mut output).unwrap();
+
+        assert_eq!(data, output);
+    }
+
+    #[test]
+    fn test_code_lengths_beyond_8_bits() {
+        // Fibonacci-weighted frequencies produce a maximally skewed tree
+        // whose deepest leaf needs more bits than a u8 code could hold.
+        let fib = [1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144];
+        let test_string: String = fib
+            .iter()
+            .enumerate()
+            .flat_map(|(i, count)| std::iter::repeat_n((b'a' + i as u8) as char, *count))
+            .collect();
+
+        let huffman_codes = generate_huffman_str(&test_string);
+        let longest = huffman_codes.values().map(|code| code.len).max().unwrap();
+        assert!(longest > 8);
+
+        let compressed = compress_str(&test_string, &huffman_codes);
+        assert_eq!(
+            test_string.as_bytes(),
+            decompress(&compressed, &huffman_codes, test_string.len())
+        );
+    }
 }